@@ -0,0 +1,677 @@
+//! Simple HTLC-style escrow, kept for backward compatibility with early
+//! integrations while `fusion_simple` becomes the primary entrypoint.
+//!
+//! Unlike a plain single-deadline HTLC, escrows here move through four
+//! sequential phases measured from the moment they are funded, mirroring
+//! the withdrawal phases of 1inch Fusion+:
+//!
+//! 1. **Finality** - no claim or cancel is allowed yet.
+//! 2. **Exclusive** - only the designated taker, provided they are also a
+//!    whitelisted resolver, may `claim`.
+//! 3. **Public withdraw** - anyone holding the secret may `claim` on the
+//!    taker's behalf; the resolver whitelist no longer applies.
+//! 4. **Cancellation** - the maker has a priority window to `cancel` and
+//!    recover the funds; after that window, anyone may trigger it for a
+//!    safety-deposit reward.
+//!
+//! An admin, set once via `set_admin`, curates the resolver whitelist with
+//! `add_resolver`/`remove_resolver`, analogous to role-gated access control
+//! in OZ-style contracts.
+
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes, BytesN, Env, Vec};
+
+#[contracterror]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EscrowError {
+    NotFound = 1,
+    AlreadyExists = 2,
+    InvalidAmount = 3,
+    NotFunded = 4,
+    AlreadyFunded = 5,
+    AlreadyCompleted = 6,
+    InvalidSecret = 7,
+    TimelockNotExpired = 8,
+    StillInFinalityLock = 9,
+    NotExclusiveResolver = 10,
+    PublicPhaseNotStarted = 11,
+    /// Neither or both of (hashlock) / (merkle_root, parts) were supplied,
+    /// or the wrong claim entrypoint was used for this escrow's lock mode.
+    InvalidLockMode = 12,
+    InvalidMerkleProof = 13,
+    InvalidIndex = 14,
+    InvalidHashKind = 15,
+    /// Non-positive `safety_deposit` supplied to `create_escrow`. Named for
+    /// what actually fails validation, not for a deposit being absent.
+    InvalidSafetyDeposit = 16,
+    NotAuthorized = 17,
+    ResolverNotWhitelisted = 18,
+    ArbitratorOnly = 19,
+    HashlockReused = 20,
+    /// `claim`/`claim_partial` called once the cancellation phase has been
+    /// reached; the claim window has closed and only `cancel` is valid.
+    ClaimWindowClosed = 21,
+}
+
+/// Withdrawal phase an escrow is currently in, derived from `funded_at`
+/// and the ledger's current sequence number.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Phase {
+    Finality,
+    Exclusive,
+    PublicWithdraw,
+    Cancellation,
+}
+
+/// Lifecycle status an escrow has reached, indexed on-chain so off-chain
+/// indexers and resolver bots can discover escrows without scanning every
+/// id.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    Created,
+    Funded,
+    Claimed,
+    Cancelled,
+}
+
+/// Which hash function a single-secret hashlock was committed with. The
+/// Ethereum side of an ETH<->XLM swap almost universally uses keccak256, so
+/// this must match whatever the counterpart HTLC expects.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashKind {
+    Sha256,
+    Keccak256,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct EscrowData {
+    pub maker: Address,
+    pub taker: Address,
+    /// Optional trusted third party who may `arbitrate` the escrow without
+    /// the secret, settling it to either side when the normal claim/timelock
+    /// paths are unavailable (e.g. the counterpart chain's leg failed).
+    pub arbitrator: Option<Address>,
+    pub amount: i128,
+    pub token: Option<Address>,
+    /// Deposit, denominated in the same `token` as the principal rather
+    /// than always native XLM, so it also works for escrows whose
+    /// principal isn't XLM. Locked alongside the principal and paid out in
+    /// full to whoever actually executes `cancel` during the cancellation
+    /// phase, as a reward for unwinding an abandoned escrow.
+    pub safety_deposit: Option<i128>,
+    /// Single-secret mode. Mutually exclusive with `merkle_root`/`parts`.
+    pub hashlock: Option<BytesN<32>>,
+    /// Hash function the `hashlock` was committed with. Ignored in
+    /// partial-fill mode, where the Merkle tree always uses sha256.
+    pub hash_kind: HashKind,
+    /// Partial-fill mode: root of a Merkle tree of `parts + 1` hashlocks,
+    /// where the secret at index `i` unlocks the cumulative fill up to the
+    /// `i`-th fraction. Mutually exclusive with `hashlock`. `claim_partial`
+    /// does not check `parts` or tie `index` to a fraction: any leaf with a
+    /// higher index than the last releases the `fill_amount` passed in, up
+    /// to `amount` in total.
+    pub merkle_root: Option<BytesN<32>>,
+    pub parts: Option<u32>,
+    /// Cumulative amount released so far under partial-fill mode.
+    pub filled_amount: i128,
+    /// Highest Merkle index claimed so far; indices must strictly increase.
+    pub last_index: Option<u32>,
+    /// Phase lengths in ledgers, laid end to end from `funded_at`:
+    /// finality, then the exclusive window, then the public window, after
+    /// which cancellation opens. `cancel` is the length of the maker's
+    /// priority window at the start of the cancellation phase.
+    pub finality: u64,
+    pub exclusive: u64,
+    pub public_withdraw: u64,
+    pub cancel: u64,
+    pub funded: bool,
+    pub completed: bool,
+    pub funded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Escrow(BytesN<32>),
+    EscrowCount,
+    Admin,
+    Resolver(Address),
+    /// Append-only list of escrow ids, keyed by maker/taker address or by
+    /// lifecycle status, for off-chain discovery without a full scan.
+    MakerIndex(Address),
+    TakerIndex(Address),
+    StatusIndex(EscrowStatus),
+    /// Nullifier registry: single-secret hashlocks consumed by a successful
+    /// `claim`, so the same leaked secret can't be replayed against a second
+    /// escrow created with the same hashlock.
+    SpentHashlock(BytesN<32>),
+}
+
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    /// Create a new escrow, locking in the amounts and the four relative
+    /// phase offsets (finality, exclusive, public_withdraw, cancel).
+    ///
+    /// Exactly one of `hashlock` (single-secret mode) or `merkle_root` +
+    /// `parts` (partial-fill mode, settled via `claim_partial`) must be set.
+    ///
+    /// `safety_deposit`, if set, is an amount in the same `token` as the
+    /// principal (not native XLM, so it also works for non-XLM-denominated
+    /// escrows), locked alongside it and paid out in full to whoever
+    /// executes `cancel`.
+    ///
+    /// `arbitrator`, if set, may `arbitrate` the escrow without the secret;
+    /// escrows created with `None` behave exactly as if no arbitrator
+    /// feature existed.
+    pub fn create_escrow(
+        env: Env,
+        escrow_id: BytesN<32>,
+        maker: Address,
+        taker: Address,
+        arbitrator: Option<Address>,
+        amount: i128,
+        token: Option<Address>,
+        safety_deposit: Option<i128>,
+        hashlock: Option<BytesN<32>>,
+        hash_kind: Option<HashKind>,
+        merkle_root: Option<BytesN<32>>,
+        parts: Option<u32>,
+        finality: u64,
+        exclusive: u64,
+        public_withdraw: u64,
+        cancel: u64,
+    ) -> Result<(), EscrowError> {
+        maker.require_auth();
+
+        if amount <= 0 {
+            return Err(EscrowError::InvalidAmount);
+        }
+        if matches!(safety_deposit, Some(deposit) if deposit <= 0) {
+            return Err(EscrowError::InvalidSafetyDeposit);
+        }
+        match (&hashlock, &merkle_root, &parts) {
+            (Some(_), None, None) => {}
+            (None, Some(_), Some(parts)) if *parts > 0 => {}
+            _ => return Err(EscrowError::InvalidLockMode),
+        }
+        let hash_kind = hash_kind.unwrap_or(HashKind::Sha256);
+        if merkle_root.is_some() && hash_kind != HashKind::Sha256 {
+            return Err(EscrowError::InvalidHashKind);
+        }
+        if let Some(hashlock) = &hashlock {
+            if Self::is_hashlock_spent(env.clone(), hashlock.clone()) {
+                return Err(EscrowError::HashlockReused);
+            }
+        }
+
+        let key = DataKey::Escrow(escrow_id.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(EscrowError::AlreadyExists);
+        }
+
+        let escrow = EscrowData {
+            maker,
+            taker,
+            arbitrator,
+            amount,
+            token,
+            safety_deposit,
+            hashlock,
+            hash_kind,
+            merkle_root,
+            parts,
+            filled_amount: 0,
+            last_index: None,
+            finality,
+            exclusive,
+            public_withdraw,
+            cancel,
+            funded: false,
+            completed: false,
+            funded_at: 0,
+        };
+        env.storage().persistent().set(&key, &escrow);
+
+        Self::push_index(&env, DataKey::MakerIndex(escrow.maker.clone()), &escrow_id);
+        Self::push_index(&env, DataKey::TakerIndex(escrow.taker.clone()), &escrow_id);
+        Self::push_index(&env, DataKey::StatusIndex(EscrowStatus::Created), &escrow_id);
+
+        let count = Self::get_escrow_count(env.clone()) + 1;
+        env.storage().instance().set(&DataKey::EscrowCount, &count);
+
+        env.events().publish((symbol_short!("escrow_created"),), escrow_id);
+
+        Ok(())
+    }
+
+    /// Mark an escrow as funded, starting its phase clock. Pulls the
+    /// principal, and the safety deposit if any, from the maker into the
+    /// contract.
+    pub fn fund_escrow(env: Env, escrow_id: BytesN<32>) -> Result<(), EscrowError> {
+        let key = DataKey::Escrow(escrow_id.clone());
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(EscrowError::NotFound)?;
+
+        if escrow.funded {
+            return Err(EscrowError::AlreadyFunded);
+        }
+
+        escrow.maker.require_auth();
+
+        let contract = env.current_contract_address();
+        Self::transfer(&env, &escrow.token, &escrow.maker, &contract, escrow.amount);
+        if let Some(deposit) = escrow.safety_deposit {
+            Self::transfer(&env, &escrow.token, &escrow.maker, &contract, deposit);
+        }
+
+        escrow.funded = true;
+        escrow.funded_at = env.ledger().sequence().into();
+        env.storage().persistent().set(&key, &escrow);
+
+        Self::push_index(&env, DataKey::StatusIndex(EscrowStatus::Funded), &escrow_id);
+
+        env.events().publish((symbol_short!("escrow_funded"),), escrow_id);
+
+        Ok(())
+    }
+
+    /// Claim the escrow by revealing the secret behind its hashlock,
+    /// transferring the principal to the taker and refunding any
+    /// `safety_deposit` to the maker. Which callers may succeed depends on
+    /// the current withdrawal phase. Rejects a hashlock already consumed by
+    /// another escrow's claim, even if this escrow was created before that
+    /// claim happened. Only valid for escrows created in single-secret
+    /// mode; use `claim_partial` for Merkle-based partial-fill escrows.
+    pub fn claim(env: Env, escrow_id: BytesN<32>, secret: BytesN<32>, caller: Address) -> Result<(), EscrowError> {
+        let key = DataKey::Escrow(escrow_id.clone());
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(EscrowError::NotFound)?;
+
+        let hashlock = escrow.hashlock.clone().ok_or(EscrowError::InvalidLockMode)?;
+        if Self::is_hashlock_spent(env.clone(), hashlock.clone()) {
+            return Err(EscrowError::HashlockReused);
+        }
+        let phase = Self::check_claimable(&env, &escrow, &caller)?;
+
+        let hash = match escrow.hash_kind {
+            HashKind::Sha256 => env.crypto().sha256(&secret),
+            HashKind::Keccak256 => env.crypto().keccak256(&secret),
+        };
+        if hash != hashlock {
+            return Err(EscrowError::InvalidSecret);
+        }
+
+        escrow.completed = true;
+        env.storage().persistent().set(&key, &escrow);
+        env.storage().persistent().set(&DataKey::SpentHashlock(hashlock), &true);
+
+        let contract = env.current_contract_address();
+        Self::transfer(&env, &escrow.token, &contract, &escrow.taker, escrow.amount);
+        if let Some(deposit) = escrow.safety_deposit {
+            Self::transfer(&env, &escrow.token, &contract, &escrow.maker, deposit);
+        }
+
+        Self::push_index(&env, DataKey::StatusIndex(EscrowStatus::Claimed), &escrow_id);
+
+        env.events().publish((symbol_short!("escrow_claimed"), phase), escrow_id);
+
+        Ok(())
+    }
+
+    /// Claim an incremental slice of a Merkle-based partial-fill escrow.
+    /// `index` must strictly increase between calls and `merkle_proof` must
+    /// verify `sha256(index || secret)` up to the stored root using the
+    /// provided sibling hashes. The escrow is marked `completed` once
+    /// `filled_amount` reaches `amount`.
+    pub fn claim_partial(
+        env: Env,
+        escrow_id: BytesN<32>,
+        fill_amount: i128,
+        secret: BytesN<32>,
+        merkle_proof: Vec<BytesN<32>>,
+        index: u32,
+        caller: Address,
+    ) -> Result<(), EscrowError> {
+        let key = DataKey::Escrow(escrow_id.clone());
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(EscrowError::NotFound)?;
+
+        let root = escrow.merkle_root.clone().ok_or(EscrowError::InvalidLockMode)?;
+        let phase = Self::check_claimable(&env, &escrow, &caller)?;
+
+        if fill_amount <= 0 || escrow.filled_amount + fill_amount > escrow.amount {
+            return Err(EscrowError::InvalidAmount);
+        }
+        if let Some(last_index) = escrow.last_index {
+            if index <= last_index {
+                return Err(EscrowError::InvalidIndex);
+            }
+        }
+
+        let leaf = Self::merkle_leaf(&env, index, &secret);
+        if Self::merkle_root(&env, leaf, index, &merkle_proof) != root {
+            return Err(EscrowError::InvalidMerkleProof);
+        }
+
+        escrow.filled_amount += fill_amount;
+        escrow.last_index = Some(index);
+        if escrow.filled_amount == escrow.amount {
+            escrow.completed = true;
+        }
+        env.storage().persistent().set(&key, &escrow);
+
+        let contract = env.current_contract_address();
+        Self::transfer(&env, &escrow.token, &contract, &escrow.taker, fill_amount);
+
+        if escrow.completed {
+            Self::push_index(&env, DataKey::StatusIndex(EscrowStatus::Claimed), &escrow_id);
+        }
+
+        env.events()
+            .publish((symbol_short!("escrow_partial"), phase, index), (escrow_id, fill_amount));
+
+        Ok(())
+    }
+
+    /// Cancel the escrow once the cancellation phase has been reached,
+    /// returning the principal to the maker. For the first `cancel`
+    /// ledgers of that phase only the maker may call it; after that it is
+    /// permissionless, and `caller` receives the `safety_deposit` (if any)
+    /// as a reward for unwinding an abandoned escrow.
+    pub fn cancel(env: Env, escrow_id: BytesN<32>, caller: Address) -> Result<(), EscrowError> {
+        caller.require_auth();
+
+        let key = DataKey::Escrow(escrow_id.clone());
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(EscrowError::NotFound)?;
+
+        if !escrow.funded {
+            return Err(EscrowError::NotFunded);
+        }
+        if escrow.completed {
+            return Err(EscrowError::AlreadyCompleted);
+        }
+        if Self::phase(&env, &escrow) != Phase::Cancellation {
+            return Err(EscrowError::TimelockNotExpired);
+        }
+        let elapsed = env.ledger().sequence().saturating_sub(escrow.funded_at as u32) as u64;
+        let maker_priority_ends = Self::cancellation_start(&escrow) + escrow.cancel;
+        if elapsed < maker_priority_ends && caller != escrow.maker {
+            return Err(EscrowError::NotAuthorized);
+        }
+
+        escrow.completed = true;
+        env.storage().persistent().set(&key, &escrow);
+
+        let contract = env.current_contract_address();
+        Self::transfer(&env, &escrow.token, &contract, &escrow.maker, escrow.amount);
+        if let Some(deposit) = escrow.safety_deposit {
+            Self::transfer(&env, &escrow.token, &contract, &caller, deposit);
+        }
+
+        Self::push_index(&env, DataKey::StatusIndex(EscrowStatus::Cancelled), &escrow_id);
+
+        env.events().publish(
+            (symbol_short!("escrow_cancelled"), Phase::Cancellation),
+            (escrow_id, caller, escrow.safety_deposit),
+        );
+
+        Ok(())
+    }
+
+    /// Let the named arbitrator settle a disputed escrow without revealing
+    /// the secret, once the finality lock has passed. Sends the principal
+    /// and any `safety_deposit` to the taker when `release_to_taker` is
+    /// true, or back to the maker otherwise. Useful when one chain's leg of
+    /// the swap has failed or a resolver misbehaves and neither the normal
+    /// claim nor the timelock path is appropriate yet. Only available when
+    /// `arbitrator` was set at creation.
+    pub fn arbitrate(env: Env, escrow_id: BytesN<32>, release_to_taker: bool) -> Result<(), EscrowError> {
+        let key = DataKey::Escrow(escrow_id.clone());
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(EscrowError::NotFound)?;
+
+        let arbitrator = escrow.arbitrator.clone().ok_or(EscrowError::ArbitratorOnly)?;
+        arbitrator.require_auth();
+
+        if !escrow.funded {
+            return Err(EscrowError::NotFunded);
+        }
+        if escrow.completed {
+            return Err(EscrowError::AlreadyCompleted);
+        }
+        if Self::phase(&env, &escrow) == Phase::Finality {
+            return Err(EscrowError::StillInFinalityLock);
+        }
+
+        escrow.completed = true;
+        env.storage().persistent().set(&key, &escrow);
+
+        let contract = env.current_contract_address();
+        let recipient = if release_to_taker { &escrow.taker } else { &escrow.maker };
+        Self::transfer(&env, &escrow.token, &contract, recipient, escrow.amount);
+        if let Some(deposit) = escrow.safety_deposit {
+            Self::transfer(&env, &escrow.token, &contract, recipient, deposit);
+        }
+
+        Self::push_index(&env, DataKey::StatusIndex(EscrowStatus::Claimed), &escrow_id);
+
+        env.events()
+            .publish((symbol_short!("escrow_arbitrated"),), (escrow_id, release_to_taker));
+
+        Ok(())
+    }
+
+    pub fn get_escrow(env: Env, escrow_id: BytesN<32>) -> Option<EscrowData> {
+        env.storage().persistent().get(&DataKey::Escrow(escrow_id))
+    }
+
+    pub fn get_escrow_count(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::EscrowCount).unwrap_or(0)
+    }
+
+    pub fn extend_escrow_ttl(env: Env, escrow_id: BytesN<32>, extend_to: u32) {
+        let key = DataKey::Escrow(escrow_id);
+        env.storage().persistent().extend_ttl(&key, extend_to, extend_to);
+    }
+
+    /// Escrow ids created by `maker`, oldest first, paginated by `start`
+    /// (offset) and `limit` (max items returned).
+    pub fn list_by_maker(env: Env, maker: Address, start: u32, limit: u32) -> Vec<BytesN<32>> {
+        let list = Self::get_index(&env, DataKey::MakerIndex(maker));
+        Self::paginate(&env, &list, start, limit)
+    }
+
+    /// Escrow ids naming `taker` as the counterparty, oldest first, paginated
+    /// by `start` (offset) and `limit` (max items returned).
+    pub fn list_by_taker(env: Env, taker: Address, start: u32, limit: u32) -> Vec<BytesN<32>> {
+        let list = Self::get_index(&env, DataKey::TakerIndex(taker));
+        Self::paginate(&env, &list, start, limit)
+    }
+
+    /// Escrow ids currently (or ever) at `status`, oldest first, paginated
+    /// by `start` (offset) and `limit` (max items returned). An escrow
+    /// appears once per status it has passed through.
+    pub fn list_by_status(env: Env, status: EscrowStatus, start: u32, limit: u32) -> Vec<BytesN<32>> {
+        let list = Self::get_index(&env, DataKey::StatusIndex(status));
+        Self::paginate(&env, &list, start, limit)
+    }
+
+    /// Move `amount` of `token` between parties via the standard token
+    /// interface. A no-op when `token` is `None`, i.e. for escrows that
+    /// were never configured with a custody asset.
+    fn transfer(env: &Env, token: &Option<Address>, from: &Address, to: &Address, amount: i128) {
+        if let Some(token) = token {
+            token::Client::new(env, token).transfer(from, to, &amount);
+        }
+    }
+
+    /// Append `escrow_id` to the append-only list stored under `key`.
+    fn push_index(env: &Env, key: DataKey, escrow_id: &BytesN<32>) {
+        let mut list = Self::get_index(env, key.clone());
+        list.push_back(escrow_id.clone());
+        env.storage().persistent().set(&key, &list);
+    }
+
+    fn get_index(env: &Env, key: DataKey) -> Vec<BytesN<32>> {
+        env.storage().persistent().get(&key).unwrap_or(Vec::new(env))
+    }
+
+    fn paginate(env: &Env, list: &Vec<BytesN<32>>, start: u32, limit: u32) -> Vec<BytesN<32>> {
+        let mut page = Vec::new(env);
+        let end = start.saturating_add(limit).min(list.len());
+        let mut i = start;
+        while i < end {
+            page.push_back(list.get(i).unwrap());
+            i += 1;
+        }
+        page
+    }
+
+    /// Set the contract admin. Callable by anyone the first time (bootstrap);
+    /// afterwards only the current admin may hand the role off.
+    pub fn set_admin(env: Env, admin: Address) -> Result<(), EscrowError> {
+        match env.storage().instance().get::<_, Address>(&DataKey::Admin) {
+            Some(current) => current.require_auth(),
+            None => admin.require_auth(),
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Whitelist a resolver address, authorized for `claim` during the
+    /// exclusive-withdrawal phase. Admin-only.
+    pub fn add_resolver(env: Env, resolver: Address) -> Result<(), EscrowError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(EscrowError::NotAuthorized)?;
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Resolver(resolver), &true);
+        Ok(())
+    }
+
+    /// Remove a resolver from the whitelist. Admin-only.
+    pub fn remove_resolver(env: Env, resolver: Address) -> Result<(), EscrowError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(EscrowError::NotAuthorized)?;
+        admin.require_auth();
+        env.storage().instance().remove(&DataKey::Resolver(resolver));
+        Ok(())
+    }
+
+    pub fn is_resolver(env: Env, resolver: Address) -> bool {
+        env.storage().instance().has(&DataKey::Resolver(resolver))
+    }
+
+    /// Returns whether `hashlock` has already been consumed by a successful
+    /// `claim`, i.e. whether it's blocked from being reused in a new escrow.
+    pub fn is_hashlock_spent(env: Env, hashlock: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::SpentHashlock(hashlock))
+    }
+
+    /// Shared claim gating: funded/not-yet-completed and phase-based caller
+    /// authorization. Returns the phase the claim was allowed under.
+    fn check_claimable(env: &Env, escrow: &EscrowData, caller: &Address) -> Result<Phase, EscrowError> {
+        if !escrow.funded {
+            return Err(EscrowError::NotFunded);
+        }
+        if escrow.completed {
+            return Err(EscrowError::AlreadyCompleted);
+        }
+
+        let phase = Self::phase(env, escrow);
+        match phase {
+            Phase::Finality => return Err(EscrowError::StillInFinalityLock),
+            Phase::Exclusive => {
+                if caller != &escrow.taker {
+                    return Err(EscrowError::NotExclusiveResolver);
+                }
+                if !Self::is_resolver(env.clone(), caller.clone()) {
+                    return Err(EscrowError::ResolverNotWhitelisted);
+                }
+                caller.require_auth();
+            }
+            Phase::PublicWithdraw => {
+                // Any party holding the secret may claim on the taker's behalf.
+            }
+            Phase::Cancellation => return Err(EscrowError::ClaimWindowClosed),
+        }
+
+        Ok(phase)
+    }
+
+    /// `sha256(index_be_bytes || secret)`, the leaf hash for a Merkle
+    /// partial-fill tree.
+    fn merkle_leaf(env: &Env, index: u32, secret: &BytesN<32>) -> BytesN<32> {
+        let mut data = Bytes::from_array(env, &index.to_be_bytes());
+        data.append(&Bytes::from_array(env, &secret.to_array()));
+        env.crypto().sha256(&data)
+    }
+
+    /// Fold `leaf` up to the root using `proof`, picking left/right order at
+    /// each level from the corresponding bit of `index`.
+    fn merkle_root(env: &Env, mut node: BytesN<32>, mut index: u32, proof: &Vec<BytesN<32>>) -> BytesN<32> {
+        for sibling in proof.iter() {
+            let (left, right) = if index & 1 == 0 {
+                (node.to_array(), sibling.to_array())
+            } else {
+                (sibling.to_array(), node.to_array())
+            };
+            let mut data = Bytes::from_array(env, &left);
+            data.append(&Bytes::from_array(env, &right));
+            node = env.crypto().sha256(&data);
+            index /= 2;
+        }
+        node
+    }
+
+    fn phase(env: &Env, escrow: &EscrowData) -> Phase {
+        let elapsed = env.ledger().sequence().saturating_sub(escrow.funded_at as u32) as u64;
+        let exclusive_start = escrow.finality;
+        let public_start = exclusive_start + escrow.exclusive;
+        let cancel_start = Self::cancellation_start(escrow);
+
+        if elapsed < exclusive_start {
+            Phase::Finality
+        } else if elapsed < public_start {
+            Phase::Exclusive
+        } else if elapsed < cancel_start {
+            Phase::PublicWithdraw
+        } else {
+            Phase::Cancellation
+        }
+    }
+
+    /// Ledger offset, relative to `funded_at`, at which the cancellation
+    /// phase begins.
+    fn cancellation_start(escrow: &EscrowData) -> u64 {
+        escrow.finality + escrow.exclusive + escrow.public_withdraw
+    }
+}
@@ -2,20 +2,27 @@
 
 use super::*;
 use soroban_sdk::{
-    symbol_short, testutils::{Address as _, Ledger, Events}, vec, Env, BytesN
+    symbol_short, testutils::{Address as _, Ledger, Events}, token, vec, Env, BytesN
 };
 
 fn create_test_env() -> (Env, Address, Address, Address) {
     let env = Env::default();
+    env.mock_all_auths();
     let contract_id = env.register_contract(None, EscrowContract);
     let alice = Address::generate(&env); // maker
     let bob = Address::generate(&env);   // taker
     (env, contract_id, alice, bob)
 }
 
+/// Deploys a real Stellar Asset Contract so tests that exercise fund
+/// movement can transfer and check balances, not just mock an address.
 fn create_test_token(env: &Env) -> Address {
-    // Mock a token contract address for testing
-    Address::generate(env)
+    let admin = Address::generate(env);
+    env.register_stellar_asset_contract(admin)
+}
+
+fn mint(env: &Env, token: &Address, to: &Address, amount: i128) {
+    token::StellarAssetClient::new(env, token).mint(to, &amount);
 }
 
 fn generate_secret_and_hash(env: &Env) -> (BytesN<32>, BytesN<32>) {
@@ -24,39 +31,84 @@ fn generate_secret_and_hash(env: &Env) -> (BytesN<32>, BytesN<32>) {
     (secret, hashlock)
 }
 
+// Phase offsets used throughout: 10 ledgers of finality, then a 10-ledger
+// exclusive window for the taker, then a 10-ledger public window, then
+// cancellation opens.
+const FINALITY: u64 = 10;
+const EXCLUSIVE: u64 = 10;
+const PUBLIC_WITHDRAW: u64 = 10;
+const CANCEL: u64 = 10;
+
+fn advance(env: &Env, by: u64) {
+    env.ledger().with_mut(|li| {
+        li.sequence_number += by as u32;
+    });
+}
+
+/// Builds a 2-leaf Merkle tree (indices 0 and 1) out of two secrets and
+/// returns (root, secrets, proofs).
+fn build_merkle_pair(env: &Env) -> (BytesN<32>, [BytesN<32>; 2], [soroban_sdk::Vec<BytesN<32>>; 2]) {
+    let secret0 = BytesN::from_array(env, &[10; 32]);
+    let secret1 = BytesN::from_array(env, &[20; 32]);
+
+    let leaf = |index: u32, secret: &BytesN<32>| {
+        let mut data = soroban_sdk::Bytes::from_array(env, &index.to_be_bytes());
+        data.append(&soroban_sdk::Bytes::from_array(env, &secret.to_array()));
+        env.crypto().sha256(&data)
+    };
+    let leaf0 = leaf(0, &secret0);
+    let leaf1 = leaf(1, &secret1);
+
+    let mut data = soroban_sdk::Bytes::from_array(env, &leaf0.to_array());
+    data.append(&soroban_sdk::Bytes::from_array(env, &leaf1.to_array()));
+    let root = env.crypto().sha256(&data);
+
+    (
+        root,
+        [secret0, secret1],
+        [vec![env, leaf1.clone()], vec![env, leaf0.clone()]],
+    )
+}
+
 #[test]
 fn test_create_escrow_success() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
     let token = Some(create_test_token(&env));
     let (_, hashlock) = generate_secret_and_hash(&env);
-    let timelock_duration = 100u64;
 
-    // Create escrow
-    let result = client.create_escrow(
+    let result = client.try_create_escrow(
         &escrow_id,
+        &alice,
         &bob,
+        &None,
         &amount,
         &token,
-        &hashlock,
-        &timelock_duration,
+        &None,
+        &Some(hashlock.clone()),
+        &None,
+        &None,
+        &None,
+        &FINALITY,
+        &EXCLUSIVE,
+        &PUBLIC_WITHDRAW,
+        &CANCEL,
     );
-    
+
     assert!(result.is_ok());
 
-    // Verify escrow was created
     let escrow_data = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow_data.maker, alice);
     assert_eq!(escrow_data.taker, bob);
     assert_eq!(escrow_data.amount, amount);
     assert_eq!(escrow_data.token, token);
-    assert_eq!(escrow_data.hashlock, hashlock);
+    assert_eq!(escrow_data.hashlock, Some(hashlock));
     assert!(!escrow_data.funded);
     assert!(!escrow_data.completed);
 
-    // Check counter was incremented
     assert_eq!(client.get_escrow_count(), 1);
 }
 
@@ -64,52 +116,76 @@ fn test_create_escrow_success() {
 fn test_create_escrow_invalid_amount() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = -100i128; // Invalid amount
     let (_, hashlock) = generate_secret_and_hash(&env);
 
     let result = client.try_create_escrow(
         &escrow_id,
+        &alice,
         &bob,
+        &None,
         &amount,
         &None,
-        &hashlock,
-        &100u64,
+        &None,
+        &Some(hashlock),
+        &None,
+        &None,
+        &None,
+        &FINALITY,
+        &EXCLUSIVE,
+        &PUBLIC_WITHDRAW,
+        &CANCEL,
     );
-    
+
     assert_eq!(result, Err(Ok(EscrowError::InvalidAmount)));
 }
 
+#[test]
+fn test_create_escrow_requires_exactly_one_lock_mode() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    let (merkle_root, _, _) = build_merkle_pair(&env);
+
+    // Neither hashlock nor merkle_root/parts supplied.
+    let result = client.try_create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &None, &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidLockMode)));
+
+    // Both supplied at once.
+    let result = client.try_create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None,
+        &Some(hashlock), &None, &Some(merkle_root), &Some(2),
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidLockMode)));
+}
+
 #[test]
 fn test_create_escrow_already_exists() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
     let (_, hashlock) = generate_secret_and_hash(&env);
 
-    // Create first escrow
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &None,
-        &hashlock,
-        &100u64,
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock.clone()), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
 
-    // Try to create same escrow again
     let result = client.try_create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &None,
-        &hashlock,
-        &100u64,
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
-    
+
     assert_eq!(result, Err(Ok(EscrowError::AlreadyExists)));
 }
 
@@ -117,68 +193,164 @@ fn test_create_escrow_already_exists() {
 fn test_fund_escrow_success() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
-    let token = Some(create_test_token(&env));
+    let token_address = create_test_token(&env);
+    let token = Some(token_address.clone());
     let (_, hashlock) = generate_secret_and_hash(&env);
+    mint(&env, &token_address, &alice, amount);
 
-    // Create escrow
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &token,
-        &hashlock,
-        &100u64,
+        &escrow_id, &alice, &bob, &None, &amount, &token, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
 
-    // Mock token authorization for alice
-    env.mock_all_auths();
-
-    // Fund escrow
-    let result = client.fund_escrow(&escrow_id);
+    let result = client.try_fund_escrow(&escrow_id);
     assert!(result.is_ok());
 
-    // Verify escrow is now funded
     let escrow_data = client.get_escrow(&escrow_id).unwrap();
     assert!(escrow_data.funded);
     assert!(!escrow_data.completed);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), 0);
+    assert_eq!(token_client.balance(&contract_id), amount);
 }
 
 #[test]
-fn test_claim_escrow_success() {
+fn test_claim_rejected_during_finality() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
-    let token = Some(create_test_token(&env));
     let (secret, hashlock) = generate_secret_and_hash(&env);
 
-    // Create and fund escrow
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &token,
-        &hashlock,
-        &100u64,
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
+    client.fund_escrow(&escrow_id);
 
-    env.mock_all_auths();
+    let result = client.try_claim(&escrow_id, &secret, &bob);
+    assert_eq!(result, Err(Ok(EscrowError::StillInFinalityLock)));
+}
+
+#[test]
+fn test_claim_rejected_once_cancellation_phase_reached() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + PUBLIC_WITHDRAW + 1);
+
+    let result = client.try_claim(&escrow_id, &secret, &bob);
+    assert_eq!(result, Err(Ok(EscrowError::ClaimWindowClosed)));
+}
+
+#[test]
+fn test_claim_exclusive_window_rejects_non_taker() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
     client.fund_escrow(&escrow_id);
+    client.set_admin(&alice);
+    client.add_resolver(&bob);
+    advance(&env, FINALITY + 1);
+
+    // alice (maker) is not the designated taker during the exclusive window.
+    let result = client.try_claim(&escrow_id, &secret, &alice);
+    assert_eq!(result, Err(Ok(EscrowError::NotExclusiveResolver)));
 
-    // Claim escrow with correct secret
-    let result = client.claim(&escrow_id, &secret);
+    // The taker, who is also a whitelisted resolver, succeeds.
+    let result = client.try_claim(&escrow_id, &secret, &bob);
     assert!(result.is_ok());
+}
 
-    // Verify escrow is completed
-    let escrow_data = client.get_escrow(&escrow_id).unwrap();
-    assert!(escrow_data.funded);
-    assert!(escrow_data.completed);
+#[test]
+fn test_claim_exclusive_window_rejects_non_whitelisted_taker() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    client.set_admin(&alice);
+    advance(&env, FINALITY + 1);
+
+    // bob is the designated taker but was never whitelisted as a resolver.
+    let result = client.try_claim(&escrow_id, &secret, &bob);
+    assert_eq!(result, Err(Ok(EscrowError::ResolverNotWhitelisted)));
+}
+
+#[test]
+fn test_resolver_registry_add_remove_and_query() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    client.set_admin(&alice);
+    assert!(!client.is_resolver(&bob));
+
+    client.add_resolver(&bob);
+    assert!(client.is_resolver(&bob));
+
+    client.remove_resolver(&bob);
+    assert!(!client.is_resolver(&bob));
+}
+
+#[test]
+fn test_add_resolver_requires_admin() {
+    let (env, contract_id, _alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    // No admin has been set yet.
+    let result = client.try_add_resolver(&bob);
+    assert_eq!(result, Err(Ok(EscrowError::NotAuthorized)));
+}
+
+#[test]
+fn test_claim_public_withdraw_allows_any_caller() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    // A third party relaying the secret on the taker's behalf succeeds.
+    let result = client.try_claim(&escrow_id, &secret, &alice);
+    assert!(result.is_ok());
 
-    // Check events
     let events = env.events().all();
     let claim_event = events.iter().find(|e| {
         e.topics.get(0).unwrap() == &symbol_short!("escrow_claimed")
@@ -186,34 +358,54 @@ fn test_claim_escrow_success() {
     assert!(claim_event.is_some());
 }
 
+#[test]
+fn test_claim_transfers_principal_and_refunds_safety_deposit() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let deposit = 50i128;
+    let token_address = create_test_token(&env);
+    let token = Some(token_address.clone());
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+    mint(&env, &token_address, &alice, amount + deposit);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &token, &Some(deposit), &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    client.claim(&escrow_id, &secret, &bob);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&bob), amount);
+    assert_eq!(token_client.balance(&alice), deposit);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
 #[test]
 fn test_claim_escrow_invalid_secret() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
     let (_, hashlock) = generate_secret_and_hash(&env);
     let wrong_secret = BytesN::from_array(&env, &[2; 32]); // Wrong secret
 
-    // Create and fund escrow
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &None,
-        &hashlock,
-        &100u64,
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
-
-    env.mock_all_auths();
     client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
 
-    // Try to claim with incorrect secret
-    let result = client.try_claim(&escrow_id, &wrong_secret);
+    let result = client.try_claim(&escrow_id, &wrong_secret, &bob);
     assert_eq!(result, Err(Ok(EscrowError::InvalidSecret)));
 
-    // Verify escrow is still not completed
     let escrow_data = client.get_escrow(&escrow_id).unwrap();
     assert!(escrow_data.funded);
     assert!(!escrow_data.completed);
@@ -223,35 +415,21 @@ fn test_claim_escrow_invalid_secret() {
 fn test_cancel_escrow_success() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
     let (_, hashlock) = generate_secret_and_hash(&env);
-    let timelock_duration = 10u64;
 
-    // Create and fund escrow
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &None,
-        &hashlock,
-        &timelock_duration,
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
-
-    env.mock_all_auths();
     client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + PUBLIC_WITHDRAW + 1);
 
-    // Advance ledger beyond timelock
-    env.ledger().with_mut(|li| {
-        li.sequence_number += timelock_duration + 1;
-    });
-
-    // Cancel escrow
-    let result = client.cancel(&escrow_id);
+    let result = client.try_cancel(&escrow_id, &alice);
     assert!(result.is_ok());
 
-    // Verify escrow is completed
     let escrow_data = client.get_escrow(&escrow_id).unwrap();
     assert!(escrow_data.funded);
     assert!(escrow_data.completed);
@@ -261,43 +439,114 @@ fn test_cancel_escrow_success() {
 fn test_cancel_escrow_timelock_not_expired() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
     let (_, hashlock) = generate_secret_and_hash(&env);
-    let timelock_duration = 100u64;
 
-    // Create and fund escrow
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &None,
-        &hashlock,
-        &timelock_duration,
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
-
-    env.mock_all_auths();
     client.fund_escrow(&escrow_id);
 
-    // Try to cancel before timelock expires
-    let result = client.try_cancel(&escrow_id);
+    let result = client.try_cancel(&escrow_id, &alice);
     assert_eq!(result, Err(Ok(EscrowError::TimelockNotExpired)));
 
-    // Verify escrow is still active
     let escrow_data = client.get_escrow(&escrow_id).unwrap();
     assert!(escrow_data.funded);
     assert!(!escrow_data.completed);
 }
 
 #[test]
-fn test_get_nonexistent_escrow() {
+fn test_cancel_rejects_non_maker_during_priority_window() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (_, hashlock) = generate_secret_and_hash(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + PUBLIC_WITHDRAW + 1);
+
+    // Still within the maker's priority window; a third party is rejected...
+    let carol = Address::generate(&env);
+    let result = client.try_cancel(&escrow_id, &carol);
+    assert_eq!(result, Err(Ok(EscrowError::NotAuthorized)));
+
+    // ...but the maker may cancel right away.
+    let result = client.try_cancel(&escrow_id, &alice);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_cancel_pays_safety_deposit_to_third_party_canceller() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let deposit = 50i128;
+    let token_address = create_test_token(&env);
+    let token = Some(token_address.clone());
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    mint(&env, &token_address, &alice, amount + deposit);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &token, &Some(deposit), &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    // Past the maker's priority window, so a third party may collect the reward.
+    advance(&env, FINALITY + EXCLUSIVE + PUBLIC_WITHDRAW + CANCEL + 1);
+
+    // Neither the maker nor the taker bother to unwind it; a third party does.
+    let carol = Address::generate(&env);
+    client.cancel(&escrow_id, &carol);
+
+    let escrow_data = client.get_escrow(&escrow_id).unwrap();
+    assert!(escrow_data.completed);
+    assert_eq!(escrow_data.safety_deposit, Some(deposit));
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), amount);
+    assert_eq!(token_client.balance(&carol), deposit);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let events = env.events().all();
+    let cancel_event = events.iter().find(|e| {
+        e.topics.get(0).unwrap() == &symbol_short!("escrow_cancelled")
+    });
+    assert!(cancel_event.is_some());
+}
+
+#[test]
+fn test_create_escrow_rejects_non_positive_safety_deposit() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (_, hashlock) = generate_secret_and_hash(&env);
+
+    let result = client.try_create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &Some(0i128), &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidSafetyDeposit)));
+}
+
+#[test]
+fn test_get_nonexistent_escrow() {
+    let (env, contract_id, _alice, _bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
-    
-    // Try to get non-existent escrow
+
     let result = client.get_escrow(&escrow_id);
     assert!(result.is_none());
 }
@@ -306,33 +555,22 @@ fn test_get_nonexistent_escrow() {
 fn test_escrow_counter() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
-    // Initially counter should be 0
+
     assert_eq!(client.get_escrow_count(), 0);
 
     let (_, hashlock) = generate_secret_and_hash(&env);
 
-    // Create first escrow
     let escrow_id1 = BytesN::from_array(&env, &[1; 32]);
     client.create_escrow(
-        &escrow_id1,
-        &bob,
-        &1000i128,
-        &None,
-        &hashlock,
-        &100u64,
+        &escrow_id1, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock.clone()), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
     assert_eq!(client.get_escrow_count(), 1);
 
-    // Create second escrow
     let escrow_id2 = BytesN::from_array(&env, &[2; 32]);
     client.create_escrow(
-        &escrow_id2,
-        &bob,
-        &2000i128,
-        &None,
-        &hashlock,
-        &100u64,
+        &escrow_id2, &alice, &bob, &None, &2000i128, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
     assert_eq!(client.get_escrow_count(), 2);
 }
@@ -341,19 +579,14 @@ fn test_escrow_counter() {
 fn test_events_emission() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let amount = 1000i128;
-    let (secret, hashlock) = generate_secret_and_hash(&env);
+    let (_, hashlock) = generate_secret_and_hash(&env);
 
-    // Create escrow and check event
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &amount,
-        &None,
-        &hashlock,
-        &100u64,
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
 
     let events = env.events().all();
@@ -362,8 +595,6 @@ fn test_events_emission() {
     });
     assert!(create_event.is_some());
 
-    // Fund escrow and check event
-    env.mock_all_auths();
     client.fund_escrow(&escrow_id);
 
     let events = env.events().all();
@@ -377,20 +608,542 @@ fn test_events_emission() {
 fn test_ttl_extension() {
     let (env, contract_id, alice, bob) = create_test_env();
     let client = EscrowContractClient::new(&env, &contract_id);
-    
+
     let escrow_id = BytesN::from_array(&env, &[1; 32]);
     let (_, hashlock) = generate_secret_and_hash(&env);
 
-    // Create escrow
     client.create_escrow(
-        &escrow_id,
-        &bob,
-        &1000i128,
-        &None,
-        &hashlock,
-        &100u64,
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
     );
 
     // Extend TTL (this should not panic)
-    client.extend_escrow_ttl(&escrow_id, &17280); // 60 days
+    client.extend_escrow_ttl(&escrow_id, &17280); // ~1 day at 5s/ledger
+}
+
+#[test]
+fn test_claim_partial_fills_across_two_resolvers() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (root, secrets, proofs) = build_merkle_pair(&env);
+    let token_address = create_test_token(&env);
+    let token = Some(token_address.clone());
+    mint(&env, &token_address, &alice, 1000i128);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &token, &None, &None, &None, &Some(root), &Some(2),
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    let token_client = token::Client::new(&env, &token_address);
+
+    // First slice, claimed by the designated taker.
+    client.claim_partial(&escrow_id, &600i128, &secrets[0], &proofs[0], &0u32, &bob);
+    let escrow_data = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow_data.filled_amount, 600);
+    assert!(!escrow_data.completed);
+    assert_eq!(token_client.balance(&bob), 600);
+
+    // Second slice, claimed by a different resolver relaying the secret, but
+    // still paid out to the taker.
+    let carol = Address::generate(&env);
+    client.claim_partial(&escrow_id, &400i128, &secrets[1], &proofs[1], &1u32, &carol);
+    let escrow_data = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow_data.filled_amount, 1000);
+    assert!(escrow_data.completed);
+    assert_eq!(token_client.balance(&bob), 1000);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_claim_partial_rejects_bad_proof() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (root, secrets, proofs) = build_merkle_pair(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &None, &None, &Some(root), &Some(2),
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    // Using index 1's proof with index 0's secret should fail verification.
+    let result = client.try_claim_partial(&escrow_id, &600i128, &secrets[0], &proofs[1], &0u32, &bob);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidMerkleProof)));
+}
+
+#[test]
+fn test_claim_partial_rejects_non_increasing_index() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (root, secrets, proofs) = build_merkle_pair(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &None, &None, &Some(root), &Some(2),
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    client.claim_partial(&escrow_id, &600i128, &secrets[0], &proofs[0], &0u32, &bob);
+
+    let result = client.try_claim_partial(&escrow_id, &400i128, &secrets[1], &proofs[1], &0u32, &bob);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidIndex)));
+}
+
+#[test]
+fn test_claim_keccak256_hashlock() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let secret = BytesN::from_array(&env, &[1; 32]);
+    let hashlock = env.crypto().keccak256(&secret);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &Some(HashKind::Keccak256), &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    client.claim(&escrow_id, &secret, &bob);
+
+    let escrow_data = client.get_escrow(&escrow_id).unwrap();
+    assert!(escrow_data.completed);
+}
+
+#[test]
+fn test_claim_keccak256_hashlock_rejected_under_default_sha256() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let secret = BytesN::from_array(&env, &[1; 32]);
+    // Committed with sha256 (the default), but revealed against a
+    // keccak256-derived hashlock: the secret itself is correct but the hash
+    // function mismatch must still fail verification.
+    let hashlock = env.crypto().keccak256(&secret);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    let result = client.try_claim(&escrow_id, &secret, &bob);
+    assert_eq!(result, Err(Ok(EscrowError::InvalidSecret)));
+}
+
+#[test]
+fn test_create_escrow_rejects_keccak256_merkle_mode() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (root, _, _) = build_merkle_pair(&env);
+
+    let result = client.try_create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &None, &Some(HashKind::Keccak256), &Some(root), &Some(2),
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    assert_eq!(result, Err(Ok(EscrowError::InvalidHashKind)));
+}
+
+#[test]
+fn test_arbitrate_rejected_during_finality() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    let arbitrator = Address::generate(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &Some(arbitrator), &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+
+    let result = client.try_arbitrate(&escrow_id, &true);
+    assert_eq!(result, Err(Ok(EscrowError::StillInFinalityLock)));
+}
+
+#[test]
+fn test_arbitrate_refunds_maker_after_finality() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let token_address = create_test_token(&env);
+    let token = Some(token_address.clone());
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    let arbitrator = Address::generate(&env);
+    mint(&env, &token_address, &alice, amount);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &Some(arbitrator.clone()), &amount, &token, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + 1);
+
+    // The resolver went dark; the arbitrator refunds the maker instead of
+    // waiting out the rest of the phase sequence.
+    client.arbitrate(&escrow_id, &false);
+
+    let escrow_data = client.get_escrow(&escrow_id).unwrap();
+    assert!(escrow_data.completed);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&alice), amount);
+    assert_eq!(token_client.balance(&contract_id), 0);
+
+    let events = env.events().all();
+    let arbitrated_event = events.iter().find(|e| {
+        e.topics.get(0).unwrap() == &symbol_short!("escrow_arbitrated")
+    });
+    assert!(arbitrated_event.is_some());
+}
+
+#[test]
+fn test_arbitrate_releases_to_taker_after_finality() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let token_address = create_test_token(&env);
+    let token = Some(token_address.clone());
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    let arbitrator = Address::generate(&env);
+    mint(&env, &token_address, &alice, amount);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &Some(arbitrator), &amount, &token, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + 1);
+
+    // The counterpart leg settled off-chain; the arbitrator releases to the
+    // taker instead of waiting out the rest of the phase sequence.
+    client.arbitrate(&escrow_id, &true);
+
+    let escrow_data = client.get_escrow(&escrow_id).unwrap();
+    assert!(escrow_data.completed);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&bob), amount);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_arbitrate_pays_safety_deposit_to_settlement_recipient() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let deposit = 50i128;
+    let token_address = create_test_token(&env);
+    let token = Some(token_address.clone());
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    let arbitrator = Address::generate(&env);
+    mint(&env, &token_address, &alice, amount + deposit);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &Some(arbitrator), &amount, &token, &Some(deposit), &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + 1);
+
+    // Released to the taker: principal and deposit both follow the taker.
+    client.arbitrate(&escrow_id, &true);
+
+    let token_client = token::Client::new(&env, &token_address);
+    assert_eq!(token_client.balance(&bob), amount + deposit);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_arbitrate_rejected_without_arbitrator() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (_, hashlock) = generate_secret_and_hash(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+
+    let result = client.try_arbitrate(&escrow_id, &true);
+    assert_eq!(result, Err(Ok(EscrowError::ArbitratorOnly)));
+}
+
+#[test]
+fn test_arbitrate_rejected_once_completed() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let amount = 1000i128;
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+    let arbitrator = Address::generate(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &Some(arbitrator), &amount, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+    client.claim(&escrow_id, &secret, &bob);
+
+    let result = client.try_arbitrate(&escrow_id, &true);
+    assert_eq!(result, Err(Ok(EscrowError::AlreadyCompleted)));
+}
+
+#[test]
+fn test_list_by_maker_and_taker() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    let carol = Address::generate(&env);
+
+    let escrow_id1 = BytesN::from_array(&env, &[1; 32]);
+    client.create_escrow(
+        &escrow_id1, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock.clone()), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+
+    // A second escrow where alice is the maker again, but carol is the taker.
+    let escrow_id2 = BytesN::from_array(&env, &[2; 32]);
+    client.create_escrow(
+        &escrow_id2, &alice, &carol, &None, &2000i128, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+
+    let by_maker = client.list_by_maker(&alice, &0u32, &10u32);
+    assert_eq!(by_maker, vec![&env, escrow_id1.clone(), escrow_id2.clone()]);
+
+    let by_taker = client.list_by_taker(&bob, &0u32, &10u32);
+    assert_eq!(by_taker, vec![&env, escrow_id1]);
+
+    let by_taker_carol = client.list_by_taker(&carol, &0u32, &10u32);
+    assert_eq!(by_taker_carol, vec![&env, escrow_id2]);
+}
+
+#[test]
+fn test_list_by_status_tracks_lifecycle() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    assert_eq!(client.list_by_status(&EscrowStatus::Created, &0u32, &10u32), vec![&env, escrow_id.clone()]);
+
+    client.fund_escrow(&escrow_id);
+    assert_eq!(client.list_by_status(&EscrowStatus::Funded, &0u32, &10u32), vec![&env, escrow_id.clone()]);
+
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+    client.claim(&escrow_id, &secret, &bob);
+    assert_eq!(client.list_by_status(&EscrowStatus::Claimed, &0u32, &10u32), vec![&env, escrow_id]);
+    assert_eq!(client.list_by_status(&EscrowStatus::Cancelled, &0u32, &10u32), vec![&env]);
+}
+
+#[test]
+fn test_list_by_status_tracks_completed_partial_fill() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (root, secrets, proofs) = build_merkle_pair(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &None, &None, &Some(root), &Some(2),
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    // First slice leaves the escrow in progress, not yet under `Claimed`.
+    client.claim_partial(&escrow_id, &600i128, &secrets[0], &proofs[0], &0u32, &bob);
+    assert_eq!(client.list_by_status(&EscrowStatus::Claimed, &0u32, &10u32), vec![&env]);
+
+    // Second slice completes it.
+    client.claim_partial(&escrow_id, &400i128, &secrets[1], &proofs[1], &1u32, &bob);
+    assert_eq!(client.list_by_status(&EscrowStatus::Claimed, &0u32, &10u32), vec![&env, escrow_id]);
+}
+
+#[test]
+fn test_list_by_status_tracks_arbitrated_escrow() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (_, hashlock) = generate_secret_and_hash(&env);
+    let arbitrator = Address::generate(&env);
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &Some(arbitrator), &1000i128, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    advance(&env, FINALITY + 1);
+
+    client.arbitrate(&escrow_id, &false);
+    assert_eq!(client.list_by_status(&EscrowStatus::Claimed, &0u32, &10u32), vec![&env, escrow_id]);
+}
+
+#[test]
+fn test_list_by_maker_paginates() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let (_, hashlock) = generate_secret_and_hash(&env);
+
+    let escrow_id1 = BytesN::from_array(&env, &[1; 32]);
+    let escrow_id2 = BytesN::from_array(&env, &[2; 32]);
+    let escrow_id3 = BytesN::from_array(&env, &[3; 32]);
+    for id in [&escrow_id1, &escrow_id2, &escrow_id3] {
+        client.create_escrow(
+            id, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock.clone()), &None, &None, &None,
+            &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+        );
+    }
+
+    let page1 = client.list_by_maker(&alice, &0u32, &2u32);
+    assert_eq!(page1, vec![&env, escrow_id1, escrow_id2]);
+
+    let page2 = client.list_by_maker(&alice, &2u32, &2u32);
+    assert_eq!(page2, vec![&env, escrow_id3]);
+}
+
+#[test]
+fn test_is_hashlock_spent_tracks_claim() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id = BytesN::from_array(&env, &[1; 32]);
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    assert!(!client.is_hashlock_spent(&hashlock));
+
+    client.create_escrow(
+        &escrow_id, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock.clone()), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id);
+    assert!(!client.is_hashlock_spent(&hashlock));
+
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+    client.claim(&escrow_id, &secret, &bob);
+
+    assert!(client.is_hashlock_spent(&hashlock));
+}
+
+#[test]
+fn test_create_escrow_rejects_reused_hashlock() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id1 = BytesN::from_array(&env, &[1; 32]);
+    let escrow_id2 = BytesN::from_array(&env, &[2; 32]);
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    client.create_escrow(
+        &escrow_id1, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock.clone()), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id1);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+    client.claim(&escrow_id1, &secret, &bob);
+
+    let result = client.try_create_escrow(
+        &escrow_id2, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    assert_eq!(result, Err(Ok(EscrowError::HashlockReused)));
+}
+
+#[test]
+fn test_claim_rejects_second_escrow_sharing_already_claimed_hashlock() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id1 = BytesN::from_array(&env, &[1; 32]);
+    let escrow_id2 = BytesN::from_array(&env, &[2; 32]);
+    let (secret, hashlock) = generate_secret_and_hash(&env);
+
+    // Both escrows are created while the hashlock is still unspent, so
+    // create_escrow's own nullifier check doesn't see a conflict at
+    // creation time; only claim's nullifier check can catch the replay.
+    client.create_escrow(
+        &escrow_id1, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock.clone()), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.create_escrow(
+        &escrow_id2, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id1);
+    client.fund_escrow(&escrow_id2);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+
+    client.claim(&escrow_id1, &secret, &bob);
+
+    let result = client.try_claim(&escrow_id2, &secret, &bob);
+    assert_eq!(result, Err(Ok(EscrowError::HashlockReused)));
+}
+
+#[test]
+fn test_create_escrow_allows_distinct_hashlocks() {
+    let (env, contract_id, alice, bob) = create_test_env();
+    let client = EscrowContractClient::new(&env, &contract_id);
+
+    let escrow_id1 = BytesN::from_array(&env, &[1; 32]);
+    let escrow_id2 = BytesN::from_array(&env, &[2; 32]);
+    let (secret1, hashlock1) = generate_secret_and_hash(&env);
+    let secret2 = BytesN::from_array(&env, &[9; 32]);
+    let hashlock2 = env.crypto().sha256(&secret2);
+
+    client.create_escrow(
+        &escrow_id1, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock1), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    client.fund_escrow(&escrow_id1);
+    advance(&env, FINALITY + EXCLUSIVE + 1);
+    client.claim(&escrow_id1, &secret1, &bob);
+
+    client.create_escrow(
+        &escrow_id2, &alice, &bob, &None, &1000i128, &None, &None, &Some(hashlock2), &None, &None, &None,
+        &FINALITY, &EXCLUSIVE, &PUBLIC_WITHDRAW, &CANCEL,
+    );
+    let escrow_data = client.get_escrow(&escrow_id2).unwrap();
+    assert!(!escrow_data.completed);
 }